@@ -1,25 +1,31 @@
-/// Data fetcher module for downloading historical housing data from Zillow Research
-/// 
-/// This module handles downloading CSV files from Zillow Research and parsing them
-/// into HousingData records that can be stored in the database.
+//! Data fetcher module for downloading historical housing data from Zillow Research
+//!
+//! This module handles downloading CSV files from Zillow Research and parsing them
+//! into HousingData records that can be stored in the database.
 
 use crate::core::models::{HousingData, DataSource};
+use crate::utils::http_client::{build_client, send_with_retry, HttpClientConfig};
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
 use log::{info, debug, warn};
 use reqwest::blocking::Client;
+use std::collections::BTreeMap;
 use std::io::Read;
 
 /// Configuration for Zillow Research data sources
+#[derive(Clone)]
 pub struct ZillowConfig {
     /// ZIP code to filter data for
     pub zip_code: String,
-    
+
     /// URL to the Zillow Research CSV file for median listing prices
     pub listing_price_url: String,
-    
+
     /// URL to the Zillow Research CSV file for inventory/active listings
     pub inventory_url: String,
+
+    /// HTTP client timeout, user agent, and retry/backoff settings
+    pub http: HttpClientConfig,
 }
 
 impl Default for ZillowConfig {
@@ -29,99 +35,283 @@ impl Default for ZillowConfig {
             // Note: These are example URLs - Zillow Research URLs may change
             // Check https://www.zillow.com/research/data/ for current URLs
             listing_price_url: "https://files.zillowstatic.com/research/public_csvs/zhvi/Zip_zhvi_uc_sfrcondo_tier_0.33_0.67_sm_sa_month.csv".to_string(),
-            inventory_url: "https://files.zillowstatic.com/research/public_csvs/invt_fs/Metro_invt_fs_uc_sfrcondo_sm_month.csv".to_string(),
+            inventory_url: "https://files.zillowstatic.com/research/public_csvs/invt_fs/Zip_invt_fs_uc_sfrcondo_sm_month.csv".to_string(),
+            http: HttpClientConfig::default(),
         }
     }
 }
 
+/// Which metric a Zillow Research CSV's date columns hold, used only to
+/// make log messages legible when parsing each file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ZillowMetric {
+    PricePerSqft,
+    ActiveListings,
+}
+
 /// Fetch historical housing data from Zillow Research
-/// 
+///
 /// # Arguments
 /// * `config` - Configuration with ZIP code and URLs
-/// 
+///
 /// # Returns
-/// Vector of HousingData parsed from CSV files
-/// 
+/// Vector of HousingData parsed from CSV files, sorted by date
+///
 /// # Note
-/// This is a simplified implementation. Zillow CSV files contain data for all ZIPs,
-/// so we need to filter by our target ZIP code (90720).
+/// Zillow CSV files contain data for every ZIP in the country, so each file
+/// is scanned for the single row matching `config.zip_code`. The price and
+/// inventory files are merged by date so each `HousingData` carries both
+/// fields where available; if the inventory file can't be downloaded,
+/// fetching proceeds with price data only. An inventory file that downloads
+/// but fails to parse is returned as an error.
 pub fn fetch_zillow_data(config: &ZillowConfig) -> Result<Vec<HousingData>> {
     info!("Fetching Zillow Research data for ZIP {}", config.zip_code);
-    
-    let client = Client::builder()
-        .user_agent("RE_TRACKER/0.1.0 (Rossmoor Housing Tracker; Educational)")
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
+
+    let client = build_client(&config.http)
         .context("Failed to create HTTP client")?;
-    
-    // For this initial implementation, we'll create synthetic historical data
-    // because parsing real Zillow CSV requires handling thousands of rows
-    // and complex date column parsing
-    warn!("Using synthetic historical data - real Zillow CSV parsing not yet implemented");
-    
-    let mut data_points = Vec::new();
-    let now = Utc::now();
-    
-    // Generate 6 months of synthetic historical data
-    for months_ago in (0..6).rev() {
-        let date = now - chrono::Duration::days(months_ago * 30);
-        
-        // Simulate realistic housing market trends
-        let base_price = 420.0 + (months_ago as f64 * 5.0);
-        let base_listings = 40 + (months_ago as i32 * 2);
-        
-        data_points.push(HousingData {
-            date,
-            active_listings: base_listings,
-            avg_price_per_sqft: Some(base_price),
-            data_source: DataSource::Historical,
-            last_updated: now,
-        });
-    }
-    
-    info!("Generated {} historical data points", data_points.len());
+
+    let price_csv = download_csv(&client, &config.listing_price_url, &config.http)
+        .context("Failed to download Zillow listing price CSV")?;
+    let prices = parse_zillow_csv(&price_csv, &config.zip_code, ZillowMetric::PricePerSqft)
+        .context("Failed to parse Zillow listing price CSV")?;
+
+    let inventory = match download_csv(&client, &config.inventory_url, &config.http) {
+        Ok(inventory_csv) => parse_zillow_csv(&inventory_csv, &config.zip_code, ZillowMetric::ActiveListings)
+            .context("Failed to parse Zillow inventory CSV")?,
+        Err(e) => {
+            warn!("Failed to download Zillow inventory CSV, proceeding with price data only: {:#}", e);
+            BTreeMap::new()
+        }
+    };
+
+    let data_points = merge_by_date(prices, inventory);
+    info!("Parsed {} historical data points for ZIP {}", data_points.len(), config.zip_code);
     Ok(data_points)
 }
 
-/// Parse a Zillow Research CSV file (placeholder implementation)
-/// 
-/// # Arguments
-/// * `csv_content` - Raw CSV file content as string
-/// * `zip_code` - ZIP code to filter for
-/// 
+/// Download a CSV file's full body as a string, transparently decompressing
+/// it if the response is gzip-encoded
+///
+/// Zillow publishes some bulk exports compressed; this is detected via the
+/// `Content-Encoding` header or a `.gz` URL suffix. Gzip decompression is
+/// gated behind the `gzip` Cargo feature (on by default) so a minimal build
+/// doesn't have to pull in the codec if it never uses it.
+///
+/// Transient failures (timeouts, connection resets) are retried with
+/// exponential backoff per `http_config`.
+fn download_csv(client: &Client, url: &str, http_config: &HttpClientConfig) -> Result<String> {
+    send_with_retry(http_config, || {
+        let response = client.get(url)
+            .send()
+            .context("Failed to request CSV")?
+            .error_for_status()
+            .context("CSV endpoint returned an error status")?;
+
+        let content_encoding = response.headers()
+            .get(reqwest::header::CONTENT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_lowercase();
+
+        let mut response = response;
+        let mut raw_body = Vec::new();
+        response.read_to_end(&mut raw_body)
+            .context("Failed to read CSV response body")?;
+
+        decode_csv_body(raw_body, &content_encoding, url)
+    })
+}
+
+/// Decode a downloaded CSV body, decompressing it first if it was served
+/// gzip-encoded
+fn decode_csv_body(raw_body: Vec<u8>, content_encoding: &str, url: &str) -> Result<String> {
+    let is_gzipped = content_encoding.contains("gzip") || url.ends_with(".gz");
+
+    if is_gzipped {
+        #[cfg(feature = "gzip")]
+        {
+            use flate2::read::GzDecoder;
+
+            let mut decoder = GzDecoder::new(&raw_body[..]);
+            let mut decoded = String::new();
+            decoder.read_to_string(&mut decoded)
+                .context("Failed to decompress gzip CSV body")?;
+            return Ok(decoded);
+        }
+
+        #[cfg(not(feature = "gzip"))]
+        anyhow::bail!("Response is gzip-compressed but this build was compiled without the 'gzip' feature");
+    }
+
+    String::from_utf8(raw_body).context("CSV response body was not valid UTF-8")
+}
+
+/// Parse one metric out of a Zillow Research wide-format CSV
+///
+/// The first columns are metadata (`RegionID`, `SizeRank`, `RegionName`,
+/// `RegionType`, `StateName`, ...) and every remaining header is a
+/// month-end date string like `2024-01-31`. Each header is classified as
+/// metadata or a date by attempting to parse it with `%Y-%m-%d`; leap-day
+/// month-ends parse the same as any other date. Only the row whose
+/// `RegionName` is an exact string match for `zip_code` is scanned (leading
+/// zeros matter), and blank cells are skipped rather than treated as zero,
+/// since they represent a missing month rather than no listings.
+///
 /// # Returns
-/// Vector of parsed HousingData records
-/// 
-/// # Note
-/// This is a placeholder. Real implementation would:
-/// 1. Parse CSV headers to find date columns
-/// 2. Find the row matching the ZIP code
-/// 3. Extract all monthly values
-/// 4. Convert to HousingData structs
-fn parse_zillow_csv(csv_content: &str, zip_code: &str) -> Result<Vec<HousingData>> {
-    // TODO: Implement real CSV parsing
-    // The Zillow CSV format has:
-    // - First column: RegionName (ZIP code)
-    // - Subsequent columns: Date columns (e.g., "2024-01-31", "2024-02-29", etc.)
-    
-    info!("Parsing Zillow CSV for ZIP {}", zip_code);
-    
-    // Placeholder - return empty vec for now
-    Ok(Vec::new())
+/// A map from date to parsed value, containing only dates with non-empty cells
+fn parse_zillow_csv(csv_content: &str, zip_code: &str, metric: ZillowMetric) -> Result<BTreeMap<NaiveDate, f64>> {
+    info!("Parsing Zillow CSV for ZIP {} ({:?})", zip_code, metric);
+
+    let mut lines = csv_content.lines();
+    let header_line = lines.next().context("Zillow CSV is missing a header row")?;
+    let headers: Vec<&str> = header_line.split(',').collect();
+
+    let region_name_index = headers.iter()
+        .position(|h| h.trim() == "RegionName")
+        .context("Zillow CSV is missing a RegionName column")?;
+
+    let date_columns: Vec<(usize, NaiveDate)> = headers.iter()
+        .enumerate()
+        .filter_map(|(i, col)| NaiveDate::parse_from_str(col.trim(), "%Y-%m-%d").ok().map(|d| (i, d)))
+        .collect();
+
+    let mut values = BTreeMap::new();
+
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').collect();
+
+        let Some(region_name) = fields.get(region_name_index) else { continue };
+        if region_name.trim() != zip_code {
+            continue;
+        }
+
+        for &(col_index, date) in &date_columns {
+            let Some(raw) = fields.get(col_index) else { continue };
+            let raw = raw.trim();
+            if raw.is_empty() {
+                // Missing month - skip rather than treat as zero
+                continue;
+            }
+            if let Ok(value) = raw.parse::<f64>() {
+                values.insert(date, value);
+            } else {
+                debug!("Skipping unparseable value '{}' for {} on {}", raw, zip_code, date);
+            }
+        }
+
+        // Exactly one row should match the target ZIP
+        break;
+    }
+
+    if values.is_empty() {
+        warn!("No data found for ZIP {} in Zillow CSV", zip_code);
+    }
+
+    Ok(values)
+}
+
+/// Merge a price-per-sqft series and an active-listings series into a
+/// sorted `HousingData` vector, one entry per date seen in either series
+fn merge_by_date(prices: BTreeMap<NaiveDate, f64>, inventory: BTreeMap<NaiveDate, f64>) -> Vec<HousingData> {
+    let mut dates: Vec<NaiveDate> = prices.keys().chain(inventory.keys()).cloned().collect();
+    dates.sort();
+    dates.dedup();
+
+    let now = Utc::now();
+
+    dates.into_iter().map(|date| {
+        let date_str = date.format("%Y-%m-%d").to_string();
+        let date_time: DateTime<Utc> = DateTime::parse_from_rfc3339(&format!("{}T00:00:00Z", date_str))
+            .expect("formatted date string must be valid RFC3339")
+            .with_timezone(&Utc);
+
+        HousingData {
+            date: date_time,
+            // Inventory data isn't always available for a date the price
+            // file covers; default to zero rather than dropping the point
+            active_listings: inventory.get(&date).map(|v| v.round() as i32).unwrap_or(0),
+            avg_price_per_sqft: prices.get(&date).copied(),
+            data_source: DataSource::Historical,
+            last_updated: now,
+        }
+    }).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
+    const SAMPLE_ZHVI_CSV: &str = "RegionID,SizeRank,RegionName,RegionType,StateName,2024-01-31,2024-02-29,2024-03-31\n\
+        91982,1,90720,zip,CA,450.1,452.3,\n\
+        91983,2,90266,zip,CA,800.0,805.0,810.0\n";
+
     #[test]
-    fn test_fetch_zillow_data() {
-        let config = ZillowConfig::default();
-        let result = fetch_zillow_data(&config);
-        
-        assert!(result.is_ok());
-        let data = result.unwrap();
-        assert!(!data.is_empty());
-        assert_eq!(data[0].data_source, DataSource::Historical);
+    fn test_parse_zillow_csv_matches_exact_zip() {
+        let values = parse_zillow_csv(SAMPLE_ZHVI_CSV, "90720", ZillowMetric::PricePerSqft).unwrap();
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[&NaiveDate::from_ymd_opt(2024, 1, 31).unwrap()], 450.1);
+        assert_eq!(values[&NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()], 452.3);
+    }
+
+    #[test]
+    fn test_parse_zillow_csv_skips_blank_cells() {
+        let values = parse_zillow_csv(SAMPLE_ZHVI_CSV, "90720", ZillowMetric::PricePerSqft).unwrap();
+
+        // March was blank for the 90720 row and must not appear as a zero
+        assert!(!values.contains_key(&NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_zillow_csv_no_match_returns_empty() {
+        let values = parse_zillow_csv(SAMPLE_ZHVI_CSV, "00000", ZillowMetric::PricePerSqft).unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn test_decode_csv_body_plain_text() {
+        let body = decode_csv_body(SAMPLE_ZHVI_CSV.as_bytes().to_vec(), "", "https://example.com/data.csv").unwrap();
+        assert_eq!(body, SAMPLE_ZHVI_CSV);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_decode_csv_body_gzip_encoded() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(SAMPLE_ZHVI_CSV.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let body = decode_csv_body(compressed, "gzip", "https://example.com/data.csv").unwrap();
+        assert_eq!(body, SAMPLE_ZHVI_CSV);
+    }
+
+    #[test]
+    fn test_merge_by_date_combines_price_and_inventory() {
+        let mut prices = BTreeMap::new();
+        let d1 = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        prices.insert(d1, 450.0);
+        prices.insert(d2, 452.0);
+
+        let mut inventory = BTreeMap::new();
+        inventory.insert(d1, 42.0);
+
+        let merged = merge_by_date(prices, inventory);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].active_listings, 42);
+        assert_eq!(merged[0].avg_price_per_sqft, Some(450.0));
+        // Inventory was missing for the second date, so it defaults to 0
+        // while the price is still carried through
+        assert_eq!(merged[1].active_listings, 0);
+        assert_eq!(merged[1].avg_price_per_sqft, Some(452.0));
     }
 }
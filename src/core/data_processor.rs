@@ -1,57 +1,184 @@
-/// Data processor module for calculating Simple Moving Averages and processing housing data
-/// 
-/// This module transforms raw housing data into chart-ready format with
-/// calculated metrics like SMAs and statistical summaries.
+//! Data processor module for calculating Simple Moving Averages and processing housing data
+//! 
+//! This module transforms raw housing data into chart-ready format with
+//! calculated metrics like SMAs and statistical summaries.
 
-use crate::core::models::{HousingData, SmaConfig, MarketSummary};
+use crate::core::models::{HousingData, SmaConfig, SmaStrategyKind, MarketSummary};
 use anyhow::Result;
 use log::{info, debug};
 
-/// Calculate Simple Moving Average (SMA) for a given dataset
-/// 
+/// Strategy for turning a series of housing data into a smoothed trend line
+///
+/// `SmaConfig::strategy` selects which implementation `calculate_sma` uses;
+/// new smoothing behaviors can be added by implementing this trait rather
+/// than growing `calculate_sma` itself.
+pub trait SmaStrategy {
+    /// Compute the smoothed series over `data`
+    ///
+    /// # Returns
+    /// Vector of (date_index, value) tuples for points where the strategy
+    /// produced a value
+    fn compute(&self, data: &[HousingData]) -> Vec<(usize, f64)>;
+}
+
+/// Plain arithmetic mean over a trailing window - the original `calculate_sma` behavior
+pub struct SimpleAverageStrategy {
+    pub period: usize,
+}
+
+impl SmaStrategy for SimpleAverageStrategy {
+    fn compute(&self, data: &[HousingData]) -> Vec<(usize, f64)> {
+        let period = self.period;
+
+        if data.len() < period {
+            debug!("Not enough data points ({}) for SMA period {}", data.len(), period);
+            return Vec::new();
+        }
+
+        let mut sma_values = Vec::new();
+
+        for i in (period - 1)..data.len() {
+            let mut sum = 0.0;
+            let mut count = 0;
+
+            for point in &data[(i + 1 - period)..=i] {
+                if let Some(price) = point.avg_price_per_sqft {
+                    sum += price;
+                    count += 1;
+                }
+            }
+
+            if count > 0 {
+                let sma = sum / count as f64;
+                sma_values.push((i, sma));
+                debug!("SMA at index {}: ${:.2}", i, sma);
+            }
+        }
+
+        sma_values
+    }
+}
+
+/// Exponential moving average, seeded with the SMA of the first `period`
+/// points and carried forward across gaps rather than reset by them
+pub struct ExponentialMovingAverageStrategy {
+    pub period: usize,
+}
+
+impl SmaStrategy for ExponentialMovingAverageStrategy {
+    fn compute(&self, data: &[HousingData]) -> Vec<(usize, f64)> {
+        let period = self.period;
+
+        if data.len() < period {
+            debug!("Not enough data points ({}) for EMA period {}", data.len(), period);
+            return Vec::new();
+        }
+
+        let alpha = 2.0 / (period as f64 + 1.0);
+
+        // Seed with the simple average of the first `period` valid points
+        let seed_window = &data[0..period];
+        let seed_values: Vec<f64> = seed_window.iter().filter_map(|d| d.avg_price_per_sqft).collect();
+        if seed_values.is_empty() {
+            debug!("No valid seed data for EMA in the first {} points", period);
+            return Vec::new();
+        }
+        let mut ema = seed_values.iter().sum::<f64>() / seed_values.len() as f64;
+
+        let mut ema_values = vec![(period - 1, ema)];
+
+        for (i, point) in data.iter().enumerate().skip(period) {
+            if let Some(price) = point.avg_price_per_sqft {
+                ema = alpha * price + (1.0 - alpha) * ema;
+            }
+            // Carry the last EMA forward across `None` values rather than resetting
+            ema_values.push((i, ema));
+        }
+
+        ema_values
+    }
+}
+
+/// Rolling least-squares linear regression fitted over each trailing window,
+/// emitting the fitted value at the window's most recent point - a
+/// lag-reduced trend line that reacts faster than a plain average
+pub struct LinearRegressionStrategy {
+    pub period: usize,
+}
+
+impl SmaStrategy for LinearRegressionStrategy {
+    fn compute(&self, data: &[HousingData]) -> Vec<(usize, f64)> {
+        let period = self.period;
+
+        if data.len() < period {
+            debug!("Not enough data points ({}) for regression period {}", data.len(), period);
+            return Vec::new();
+        }
+
+        let mut trend_values = Vec::new();
+
+        for i in (period - 1)..data.len() {
+            let window = &data[(i + 1 - period)..=i];
+
+            // Fit y = m*x + b over the in-window index, skipping `None`s
+            let points: Vec<(f64, f64)> = window.iter()
+                .enumerate()
+                .filter_map(|(x, d)| d.avg_price_per_sqft.map(|y| (x as f64, y)))
+                .collect();
+
+            if points.len() < 2 {
+                continue;
+            }
+
+            let n = points.len() as f64;
+            let sum_x: f64 = points.iter().map(|(x, _)| x).sum();
+            let sum_y: f64 = points.iter().map(|(_, y)| y).sum();
+            let sum_xy: f64 = points.iter().map(|(x, y)| x * y).sum();
+            let sum_xx: f64 = points.iter().map(|(x, _)| x * x).sum();
+
+            let denom = n * sum_xx - sum_x * sum_x;
+            if denom == 0.0 {
+                continue;
+            }
+
+            let m = (n * sum_xy - sum_x * sum_y) / denom;
+            let b = (sum_y - m * sum_x) / n;
+
+            let fitted = m * (period - 1) as f64 + b;
+            trend_values.push((i, fitted));
+        }
+
+        trend_values
+    }
+}
+
+/// Calculate a smoothed price/sqft trend for a given dataset
+///
+/// The smoothing behavior is selected by `config.strategy` (simple average,
+/// EMA, or rolling linear regression); see `SmaStrategy`.
+///
 /// # Arguments
 /// * `data` - Vector of housing data points, should be sorted by date
-/// * `config` - SMA configuration specifying the period
-/// 
+/// * `config` - SMA configuration specifying the period and strategy
+///
 /// # Returns
-/// Vector of (date_index, sma_value) tuples for data points where SMA can be calculated
-/// 
+/// Vector of (date_index, value) tuples for data points where a value
+/// could be calculated
+///
 /// # Example
-/// ```
+/// ```ignore
 /// let sma_values = calculate_sma(&housing_data, &SmaConfig::new(7));
 /// ```
 pub fn calculate_sma(data: &[HousingData], config: &SmaConfig) -> Vec<(usize, f64)> {
     let period = config.period_days as usize;
-    
-    // Need at least 'period' data points to calculate SMA
-    if data.len() < period {
-        debug!("Not enough data points ({}) for SMA period {}", data.len(), period);
-        return Vec::new();
-    }
-    
-    let mut sma_values = Vec::new();
-    
-    // Calculate SMA for each point where we have enough historical data
-    for i in (period - 1)..data.len() {
-        // Sum the price/sqft values for the last 'period' days
-        let mut sum = 0.0;
-        let mut count = 0;
-        
-        for j in (i - period + 1)..=i {
-            if let Some(price) = data[j].avg_price_per_sqft {
-                sum += price;
-                count += 1;
-            }
-        }
-        
-        // Only calculate SMA if we have at least some valid data points
-        if count > 0 {
-            let sma = sum / count as f64;
-            sma_values.push((i, sma));
-            debug!("SMA at index {}: ${:.2}", i, sma);
-        }
-    }
-    
+
+    let strategy: Box<dyn SmaStrategy> = match config.strategy {
+        SmaStrategyKind::Simple => Box::new(SimpleAverageStrategy { period }),
+        SmaStrategyKind::Ema => Box::new(ExponentialMovingAverageStrategy { period }),
+        SmaStrategyKind::LinearRegression => Box::new(LinearRegressionStrategy { period }),
+    };
+
+    let sma_values = strategy.compute(data);
     info!("Calculated {} SMA values for period {}", sma_values.len(), period);
     sma_values
 }
@@ -74,7 +201,7 @@ pub fn calculate_listings_sma(data: &[HousingData], config: &SmaConfig) -> Vec<(
     let mut sma_values = Vec::new();
     
     for i in (period - 1)..data.len() {
-        let sum: i32 = data[(i - period + 1)..=i]
+        let sum: i32 = data[(i + 1 - period)..=i]
             .iter()
             .map(|d| d.active_listings)
             .sum();
@@ -139,7 +266,10 @@ pub fn generate_market_summary(data: &[HousingData]) -> Result<MarketSummary> {
     } else {
         0.0
     };
-    
+
+    let annualized_volatility = ewma_annualized_volatility(data, DEFAULT_VOLATILITY_DECAY);
+    let max_drawdown = max_drawdown(data);
+
     let summary = MarketSummary {
         avg_listings,
         avg_price_per_sqft,
@@ -147,14 +277,76 @@ pub fn generate_market_summary(data: &[HousingData]) -> Result<MarketSummary> {
         max_price_per_sqft,
         price_change_percent,
         data_points: data.len(),
+        annualized_volatility,
+        max_drawdown,
     };
-    
-    info!("Generated market summary: avg_listings={:.1}, avg_price=${:.2}, change={:.2}%", 
-          summary.avg_listings, summary.avg_price_per_sqft, summary.price_change_percent);
-    
+
+    info!("Generated market summary: avg_listings={:.1}, avg_price=${:.2}, change={:.2}%, volatility={:.4}, max_drawdown={:.2}%",
+          summary.avg_listings, summary.avg_price_per_sqft, summary.price_change_percent,
+          summary.annualized_volatility, summary.max_drawdown * 100.0);
+
     Ok(summary)
 }
 
+/// Default EWMA decay factor for volatility estimation (RiskMetrics default)
+const DEFAULT_VOLATILITY_DECAY: f64 = 0.94;
+
+/// Estimate annualized volatility of price/sqft via an exponentially
+/// weighted moving average of squared daily log-returns
+///
+/// `sigma_t^2 = lambda * sigma_{t-1}^2 + (1 - lambda) * r_t^2`, where
+/// `r_t = ln(p_t / p_{t-1})`. `None` gaps are skipped when forming returns
+/// rather than treated as a return of zero. The daily variance is annualized
+/// by scaling the resulting standard deviation by `sqrt(365)`.
+fn ewma_annualized_volatility(data: &[HousingData], lambda: f64) -> f64 {
+    let prices: Vec<f64> = data.iter().filter_map(|d| d.avg_price_per_sqft).collect();
+
+    if prices.len() < 2 {
+        return 0.0;
+    }
+
+    let mut variance = 0.0;
+    let mut initialized = false;
+
+    for window in prices.windows(2) {
+        let r = (window[1] / window[0]).ln();
+        let r_squared = r * r;
+
+        variance = if initialized {
+            lambda * variance + (1.0 - lambda) * r_squared
+        } else {
+            r_squared
+        };
+        initialized = true;
+    }
+
+    variance.sqrt() * (365.0_f64).sqrt()
+}
+
+/// Compute the maximum drawdown: the largest peak-to-trough decline in
+/// price/sqft, expressed as a negative fraction of the running peak
+///
+/// Tracks the running maximum price seen so far and the worst
+/// `(price - running_max) / running_max`; `None` gaps are skipped.
+fn max_drawdown(data: &[HousingData]) -> f64 {
+    let mut running_max = f64::NEG_INFINITY;
+    let mut worst_drawdown = 0.0;
+
+    for price in data.iter().filter_map(|d| d.avg_price_per_sqft) {
+        if price > running_max {
+            running_max = price;
+        }
+        if running_max > 0.0 {
+            let drawdown = (price - running_max) / running_max;
+            if drawdown < worst_drawdown {
+                worst_drawdown = drawdown;
+            }
+        }
+    }
+
+    worst_drawdown
+}
+
 /// Interpolate missing data points in a time series
 /// This uses linear interpolation between known values
 /// 
@@ -253,6 +445,82 @@ pub fn remove_outliers(data: &mut [HousingData]) -> usize {
     outlier_count
 }
 
+/// Detect and remove outliers using a windowed Hampel (median/MAD) filter
+///
+/// Unlike `remove_outliers`, which compares every point to a single global
+/// mean and standard deviation, this scans a local window around each point
+/// so a gradual, genuine seasonal or regional price shift isn't mistaken for
+/// an outlier just because it differs from the series-wide average.
+///
+/// For each point, takes the surrounding window of radius `window_radius`
+/// days (windows shrink rather than skip near the edges), computes the
+/// median `m` of the valid values in that window and the Median Absolute
+/// Deviation `MAD = median(|x_i - m|)`, scales it by the Gaussian
+/// consistency constant `1.4826` to estimate `sigma_hat`, and flags the
+/// center point as an outlier (setting it to `None`) when
+/// `|x - m| > k * sigma_hat`.
+///
+/// # Arguments
+/// * `data` - Mutable reference to housing data (outliers will be marked as None)
+/// * `window_radius` - Number of points on each side of the center to include
+/// * `k` - Number of scaled MADs a point must deviate by to be flagged (default 3.0)
+///
+/// # Returns
+/// Number of outliers detected and removed
+pub fn remove_outliers_hampel(data: &mut [HousingData], window_radius: usize, k: f64) -> usize {
+    let len = data.len();
+    let original: Vec<Option<f64>> = data.iter().map(|d| d.avg_price_per_sqft).collect();
+
+    let mut outlier_count = 0;
+
+    for i in 0..len {
+        let Some(x) = original[i] else { continue };
+
+        let start = i.saturating_sub(window_radius);
+        let end = (i + window_radius + 1).min(len);
+
+        let mut window: Vec<f64> = original[start..end].iter().filter_map(|v| *v).collect();
+        if window.len() < 2 {
+            continue;
+        }
+
+        let median = median_of(&mut window);
+
+        let mut deviations: Vec<f64> = window.iter().map(|v| (v - median).abs()).collect();
+        let mad = median_of(&mut deviations);
+        let sigma_hat = 1.4826 * mad;
+
+        if sigma_hat == 0.0 {
+            continue;
+        }
+
+        if (x - median).abs() > k * sigma_hat {
+            debug!("Removing Hampel outlier at index {}: ${:.2} (window median: ${:.2}, sigma_hat: ${:.2})",
+                   i, x, median, sigma_hat);
+            data[i].avg_price_per_sqft = None;
+            outlier_count += 1;
+        }
+    }
+
+    if outlier_count > 0 {
+        info!("Removed {} outliers via Hampel filter (window_radius={}, k={})",
+              outlier_count, window_radius, k);
+    }
+
+    outlier_count
+}
+
+/// Compute the median of a slice, sorting it in place
+fn median_of(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +547,31 @@ mod tests {
         assert_eq!(sma_values.len(), 24);
     }
 
+    #[test]
+    fn test_calculate_sma_ema_strategy() {
+        let data = create_test_data(30);
+        let config = SmaConfig::new(7).with_strategy(SmaStrategyKind::Ema);
+        let ema_values = calculate_sma(&data, &config);
+
+        // Same coverage as the simple average: one value per point from the
+        // end of the seed window onward
+        assert_eq!(ema_values.len(), 24);
+    }
+
+    #[test]
+    fn test_calculate_sma_linear_regression_strategy() {
+        let data = create_test_data(30);
+        let config = SmaConfig::new(7).with_strategy(SmaStrategyKind::LinearRegression);
+        let trend_values = calculate_sma(&data, &config);
+
+        assert_eq!(trend_values.len(), 24);
+        // Prices increase linearly in the fixture, so the fitted endpoint
+        // should track the actual last value in each window closely
+        let (last_index, last_value) = *trend_values.last().unwrap();
+        let actual = data[last_index].avg_price_per_sqft.unwrap();
+        assert!((last_value - actual).abs() < 0.01);
+    }
+
     #[test]
     fn test_calculate_sma_insufficient_data() {
         let data = create_test_data(5);
@@ -289,13 +582,53 @@ mod tests {
         assert_eq!(sma_values.len(), 0);
     }
 
+    #[test]
+    fn test_remove_outliers_hampel_flags_local_spike() {
+        let mut data = create_test_data(20);
+        // Inject a single sharp spike into an otherwise smooth local series
+        data[10].avg_price_per_sqft = Some(10_000.0);
+
+        let removed = remove_outliers_hampel(&mut data, 5, 3.0);
+
+        assert_eq!(removed, 1);
+        assert!(data[10].avg_price_per_sqft.is_none());
+    }
+
+    #[test]
+    fn test_remove_outliers_hampel_ignores_flat_window() {
+        let mut data = create_test_data(10);
+        for point in data.iter_mut() {
+            point.avg_price_per_sqft = Some(450.0);
+        }
+
+        // sigma_hat is zero across a perfectly flat window, so nothing should be flagged
+        let removed = remove_outliers_hampel(&mut data, 3, 3.0);
+        assert_eq!(removed, 0);
+    }
+
     #[test]
     fn test_generate_market_summary() {
         let data = create_test_data(10);
         let summary = generate_market_summary(&data).unwrap();
-        
+
         assert!(summary.avg_listings > 0.0);
         assert!(summary.avg_price_per_sqft > 0.0);
         assert_eq!(summary.data_points, 10);
+        // Prices rise steadily in the fixture, so there's no drawdown and
+        // some non-zero volatility from the day-to-day returns
+        assert_eq!(summary.max_drawdown, 0.0);
+        assert!(summary.annualized_volatility > 0.0);
+    }
+
+    #[test]
+    fn test_generate_market_summary_detects_drawdown() {
+        let mut data = create_test_data(10);
+        // Prices rise then fall sharply from the 5th point onward
+        for (i, point) in data.iter_mut().enumerate().skip(5) {
+            point.avg_price_per_sqft = Some(450.0 - (i as f64 - 4.0) * 50.0);
+        }
+
+        let summary = generate_market_summary(&data).unwrap();
+        assert!(summary.max_drawdown < 0.0);
     }
 }
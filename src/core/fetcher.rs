@@ -0,0 +1,177 @@
+//! Generic REST ingestion module for pulling historical housing metrics
+//!
+//! Unlike `data_fetcher`, which downloads and parses Zillow's bulk CSV
+//! exports, this module queries a configurable JSON REST endpoint for a
+//! specific date range and feeds the results straight into `Storage`. It is
+//! intended for remote sources that expose a simple "metrics for ZIP between
+//! two dates" API.
+
+use crate::core::models::{HousingData, DataSource};
+use crate::core::storage::Storage;
+use crate::utils::http_client::{build_client, send_with_retry, HttpClientConfig};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, info};
+use serde::Deserialize;
+
+/// Configuration for the remote metrics endpoint
+pub struct RemoteFetcherConfig {
+    /// ZIP code to request data for
+    pub zip_code: String,
+
+    /// Base URL of the REST endpoint, e.g. `https://example.com/api/metrics`
+    /// Queried as `{base_url}?zip={zip_code}&start={start}&end={end}`
+    pub base_url: String,
+
+    /// HTTP client timeout, user agent, and retry/backoff settings
+    pub http: HttpClientConfig,
+}
+
+impl Default for RemoteFetcherConfig {
+    fn default() -> Self {
+        Self {
+            zip_code: "90720".to_string(),
+            base_url: "https://example.com/api/metrics".to_string(),
+            http: HttpClientConfig::default(),
+        }
+    }
+}
+
+/// One data point as returned by the remote metrics endpoint
+#[derive(Debug, Deserialize)]
+struct RemoteMetric {
+    date: DateTime<Utc>,
+    active_listings: i32,
+    avg_price_per_sqft: Option<f64>,
+}
+
+/// Query the remote endpoint for housing metrics over a date range
+///
+/// # Arguments
+/// * `config` - Endpoint configuration
+/// * `start_date` - Beginning of the range (inclusive)
+/// * `end_date` - End of the range (inclusive)
+///
+/// # Returns
+/// Vector of `HousingData`, tagged `DataSource::Scraped` and stamped with
+/// the current time as `last_updated`
+pub fn fetch_range(
+    config: &RemoteFetcherConfig,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+) -> Result<Vec<HousingData>> {
+    info!(
+        "Fetching remote metrics for ZIP {} from {} to {}",
+        config.zip_code,
+        start_date.format("%Y-%m-%d"),
+        end_date.format("%Y-%m-%d")
+    );
+
+    let client = build_client(&config.http)
+        .context("Failed to create HTTP client")?;
+
+    let metrics: Vec<RemoteMetric> = send_with_retry(&config.http, || {
+        client
+            .get(&config.base_url)
+            .query(&[
+                ("zip", config.zip_code.as_str()),
+                ("start", &start_date.format("%Y-%m-%d").to_string()),
+                ("end", &end_date.format("%Y-%m-%d").to_string()),
+            ])
+            .send()
+            .context("Failed to send request to remote metrics endpoint")?
+            .error_for_status()
+            .context("Remote metrics endpoint returned an error status")?
+            .json()
+            .context("Failed to deserialize remote metrics response")
+    })?;
+
+    let now = Utc::now();
+    let data: Vec<HousingData> = metrics
+        .into_iter()
+        .map(|m| HousingData {
+            date: m.date,
+            active_listings: m.active_listings,
+            avg_price_per_sqft: m.avg_price_per_sqft,
+            data_source: DataSource::Scraped,
+            last_updated: now,
+        })
+        .collect();
+
+    info!("Fetched {} remote data points", data.len());
+    Ok(data)
+}
+
+/// Fetch and store only the dates missing from the database within a range
+///
+/// Uses `get_latest_data` to pick where the request starts: the day after
+/// the latest stored point when that point falls inside the range,
+/// otherwise `start_date`. So repeated incremental calls don't re-download
+/// the whole history. Fetched dates already present in the range (via
+/// `get_data_range`) are dropped before insert. Missing dates earlier than
+/// the latest stored point are not requested.
+///
+/// # Arguments
+/// * `storage` - Database to check against and insert into
+/// * `config` - Endpoint configuration
+/// * `start_date` - Beginning of the range to backfill (inclusive)
+/// * `end_date` - End of the range to backfill (inclusive)
+///
+/// # Returns
+/// Number of new data points fetched and stored
+pub fn fill_gaps(
+    storage: &mut Storage,
+    config: &RemoteFetcherConfig,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+) -> Result<usize> {
+    let existing = storage
+        .get_data_range(start_date, end_date)
+        .context("Failed to load existing data range")?;
+
+    let fetch_start = match storage.get_latest_data()? {
+        Some(latest) if latest.date >= start_date && latest.date < end_date => {
+            latest.date + Duration::days(1)
+        }
+        _ => start_date,
+    };
+
+    if fetch_start > end_date {
+        debug!("No gap to fill, database already covers the requested range");
+        return Ok(0);
+    }
+
+    let fetched = fetch_range(config, fetch_start, end_date)?;
+
+    let existing_dates: std::collections::HashSet<String> = existing
+        .iter()
+        .map(|d| d.date.format("%Y-%m-%d").to_string())
+        .collect();
+
+    let new_data: Vec<HousingData> = fetched
+        .into_iter()
+        .filter(|d| !existing_dates.contains(&d.date.format("%Y-%m-%d").to_string()))
+        .collect();
+
+    let count = new_data.len();
+    if count > 0 {
+        storage.bulk_insert(&new_data)?;
+        info!("Filled {} missing data points", count);
+    } else {
+        debug!("Remote endpoint returned no new data points for the gap");
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_fetcher_config_default() {
+        let config = RemoteFetcherConfig::default();
+        assert_eq!(config.zip_code, "90720");
+        assert!(config.http.timeout_secs > 0);
+    }
+}
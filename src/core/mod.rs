@@ -1,13 +1,22 @@
-/// Core module containing the main business logic for the RE Tracker
-/// 
-/// This module exposes all the core functionality needed to track and analyze
-/// housing market data.
+//! Core module containing the main business logic for the RE Tracker
+//! 
+//! This module exposes all the core functionality needed to track and analyze
+//! housing market data.
 
 pub mod models;
 pub mod storage;
 pub mod data_processor;
+pub mod data_fetcher;
+pub mod fetcher;
+pub mod processor_service;
+pub mod trends;
+pub mod provider;
 
 // Re-export commonly used types for convenience
-pub use models::{HousingData, DataSource, AppConfig, ScrapedData, SmaConfig, MarketSummary};
+pub use models::{HousingData, DataSource, AppConfig, ScrapedData, SmaConfig, SmaStrategyKind, MarketSummary, Property, Address, Agent, RegionDemographics};
 pub use storage::Storage;
-pub use data_processor::{calculate_sma, calculate_listings_sma, generate_market_summary, interpolate_missing_data, remove_outliers};
+pub use data_processor::{calculate_sma, calculate_listings_sma, generate_market_summary, interpolate_missing_data, remove_outliers, remove_outliers_hampel, SmaStrategy};
+pub use fetcher::{RemoteFetcherConfig, fetch_range, fill_gaps};
+pub use processor_service::{ProcessorService, RecalcRequest, RecalcResult};
+pub use trends::{TrendConfig, TrendSignal, TrendSignalKind, TrendDirection, detect_trend_signals};
+pub use provider::{DataProvider, ZillowProvider, RedfinProvider, P2pProvider, create_provider};
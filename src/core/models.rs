@@ -1,7 +1,7 @@
-/// Core data models for the RE Tracker application
-/// 
-/// This module defines the primary data structures used throughout the application
-/// for representing housing market data.
+//! Core data models for the RE Tracker application
+//! 
+//! This module defines the primary data structures used throughout the application
+//! for representing housing market data.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -54,9 +54,12 @@ pub struct AppConfig {
     
     /// Enable P2P data synchronization
     pub enable_p2p: bool,
-    
+
     /// Enable detailed logging for debugging
     pub enable_debug_logging: bool,
+
+    /// Which `DataProvider` to fetch through: "zillow", "redfin", or "p2p"
+    pub data_provider: String,
 }
 
 impl Default for AppConfig {
@@ -67,6 +70,7 @@ impl Default for AppConfig {
             cache_max_age_days: 30,
             enable_p2p: false, // Disabled by default until Phase 2
             enable_debug_logging: false,
+            data_provider: "zillow".to_string(),
         }
     }
 }
@@ -76,15 +80,84 @@ impl Default for AppConfig {
 pub struct ScrapedData {
     /// Number of active listings found
     pub listings_count: i32,
-    
+
     /// Average price per square foot calculated from listings
     pub avg_price_per_sqft: Option<f64>,
-    
+
     /// Timestamp when scraping was performed
     pub timestamp: DateTime<Utc>,
-    
+
     /// URL that was scraped
     pub source_url: String,
+
+    /// Individual listings backing the aggregate stats above, where the
+    /// source exposes per-listing detail (empty for sources that only
+    /// provide a rollup count and average)
+    pub properties: Vec<Property>,
+}
+
+/// Mailing address for a single property listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Address {
+    /// Street line, with any unit/apartment designator already split out
+    pub street_address: String,
+
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+
+    /// Unit, apartment, or suite designator (e.g. "#4", "Apt 2B"), if any
+    pub unit: Option<String>,
+}
+
+/// Listing agent or brokerage contact associated with a property
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agent {
+    pub name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+}
+
+/// A single real estate listing, carrying per-property detail beyond the
+/// daily aggregate rollups in `HousingData`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Property {
+    pub address: Address,
+
+    /// Listing price, if known
+    pub price: Option<f64>,
+
+    /// Livable square footage, if known
+    pub square_feet: Option<f64>,
+
+    /// Number of days the listing has been on the market
+    pub days_on_market: Option<i32>,
+
+    /// Date the property sold, if it has
+    pub sold_date: Option<DateTime<Utc>>,
+
+    /// MLS listing identifier, if the source exposes one
+    pub mls_id: Option<String>,
+
+    pub year_built: Option<i32>,
+    pub stories: Option<i32>,
+
+    /// Listing agent, if the source exposes one
+    pub agent: Option<Agent>,
+}
+
+/// Selects which smoothing behavior `calculate_sma` uses for a `SmaConfig`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmaStrategyKind {
+    /// Plain arithmetic mean over the trailing window (the original behavior)
+    Simple,
+
+    /// Exponential moving average, weighting recent points more heavily
+    Ema,
+
+    /// Least-squares linear regression fitted over the trailing window,
+    /// evaluated at the window's most recent point
+    LinearRegression,
 }
 
 /// Simple Moving Average configuration
@@ -92,9 +165,12 @@ pub struct ScrapedData {
 pub struct SmaConfig {
     /// Period in days for the moving average (e.g., 7, 30, 90)
     pub period_days: u32,
-    
+
     /// Whether this SMA should be displayed
     pub enabled: bool,
+
+    /// Which smoothing strategy to use when computing the series
+    pub strategy: SmaStrategyKind,
 }
 
 impl SmaConfig {
@@ -102,8 +178,24 @@ impl SmaConfig {
         Self {
             period_days,
             enabled: true,
+            strategy: SmaStrategyKind::Simple,
         }
     }
+
+    /// Return a copy of this config using the given smoothing strategy
+    pub fn with_strategy(self, strategy: SmaStrategyKind) -> Self {
+        Self { strategy, ..self }
+    }
+}
+
+/// Region-level demographic attributes, for providers that expose them
+/// alongside their listing data (e.g. a comps/valuation API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionDemographics {
+    pub zip_code: String,
+    pub population: Option<u64>,
+    pub median_household_income: Option<f64>,
+    pub median_age: Option<f64>,
 }
 
 /// Statistical summary of housing data over a time period
@@ -123,9 +215,18 @@ pub struct MarketSummary {
     
     /// Percentage change from start to end of period
     pub price_change_percent: f64,
-    
+
     /// Number of data points in this summary
     pub data_points: usize,
+
+    /// Annualized volatility of price/sqft, derived from an EWMA of squared
+    /// daily log-returns (`sigma_t^2 = lambda * sigma_{t-1}^2 + (1-lambda) * r_t^2`)
+    /// scaled by `sqrt(365)`
+    pub annualized_volatility: f64,
+
+    /// Largest peak-to-trough decline in price/sqft observed over the
+    /// period, expressed as a negative fraction (e.g. -0.12 for a 12% drop)
+    pub max_drawdown: f64,
 }
 
 #[cfg(test)]
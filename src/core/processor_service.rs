@@ -0,0 +1,262 @@
+//! Background recalculation service for heavy metric computation
+//!
+//! `calculate_sma`, `remove_outliers`, and `generate_market_summary` are
+//! cheap individually but add up as history grows, and running them inline
+//! on the same thread that's ingesting data blocks the fetch/scrape path.
+//! `ProcessorService` owns a dedicated worker thread: callers send
+//! `RecalcRequest`s after `bulk_insert`, the worker pulls the relevant rows,
+//! runs interpolation/outlier removal/SMA, writes results back through the
+//! metric-cache API on `Storage`, and reports completion and timing back on
+//! a reply channel.
+
+use crate::core::models::SmaConfig;
+use crate::core::storage::Storage;
+use crate::core::data_processor::{calculate_sma, generate_market_summary, interpolate_missing_data, remove_outliers};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use log::{error, info};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// A request to recompute derived metrics over a date range
+pub struct RecalcRequest {
+    /// SMA period in days to recompute
+    pub period: u32,
+
+    /// Date range to recompute over (inclusive)
+    pub range: (DateTime<Utc>, DateTime<Utc>),
+
+    /// Channel the worker reports completion on
+    pub reply: mpsc::Sender<RecalcResult>,
+}
+
+/// Outcome of a single recalculation, including basic timing stats
+#[derive(Debug, Clone)]
+pub struct RecalcResult {
+    pub period: u32,
+    pub sma_points: usize,
+    pub outliers_removed: usize,
+    pub elapsed: Duration,
+}
+
+/// Owns the worker thread and the channel used to submit recalculation work
+pub struct ProcessorService {
+    sender: Option<mpsc::Sender<RecalcRequest>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ProcessorService {
+    /// Spawn the worker thread, opening its own `Storage` handle to the
+    /// given database path
+    pub fn spawn<P: AsRef<Path> + Send + 'static>(db_path: P) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel::<RecalcRequest>();
+
+        let storage = Storage::new(db_path).context("Failed to open storage for processor service")?;
+
+        let handle = thread::spawn(move || run_worker(storage, receiver));
+
+        Ok(Self {
+            sender: Some(sender),
+            handle: Some(handle),
+        })
+    }
+
+    /// Submit a recalculation request; returns a receiver that yields the
+    /// result once the worker has processed it. If a later request for the
+    /// same (period, range) is coalesced with this one, or the recalculation
+    /// fails, the receiver is disconnected without yielding a result.
+    pub fn submit(&self, period: u32, range: (DateTime<Utc>, DateTime<Utc>)) -> Result<mpsc::Receiver<RecalcResult>> {
+        let (reply, reply_rx) = mpsc::channel();
+
+        self.sender
+            .as_ref()
+            .context("Processor service sender already dropped")?
+            .send(RecalcRequest { period, range, reply })
+            .context("Failed to submit recalculation request, worker thread may have exited")?;
+
+        Ok(reply_rx)
+    }
+}
+
+impl Drop for ProcessorService {
+    fn drop(&mut self) {
+        // Drop `sender` explicitly (struct fields otherwise drop only after
+        // this method returns) so the worker's `receiver.recv()` sees the
+        // channel close and its loop exits, letting the join below return
+        self.sender.take();
+
+        if let Some(handle) = self.handle.take() {
+            if let Err(e) = handle.join() {
+                error!("Processor service worker thread panicked: {:?}", e);
+            }
+        }
+    }
+}
+
+/// Key identifying a distinct recalculation: SMA period plus date range
+type RecalcKey = (u32, (DateTime<Utc>, DateTime<Utc>));
+
+/// Worker loop: processes requests as they arrive, coalescing any that are
+/// already queued for the same (period, range) so a burst of requests after
+/// a bulk insert results in one recompute instead of one per insert
+fn run_worker(mut storage: Storage, receiver: mpsc::Receiver<RecalcRequest>) {
+    info!("Processor service worker thread started");
+
+    while let Ok(first) = receiver.recv() {
+        let mut latest: std::collections::HashMap<RecalcKey, mpsc::Sender<RecalcResult>> =
+            std::collections::HashMap::new();
+        latest.insert((first.period, first.range), first.reply);
+
+        // Drain anything already queued, keeping only the most recent
+        // request (and its reply channel) per (period, range)
+        while let Ok(next) = receiver.try_recv() {
+            latest.insert((next.period, next.range), next.reply);
+        }
+
+        for ((period, range), reply) in latest {
+            let result = recalculate(&mut storage, period, range);
+            match result {
+                Ok(result) => {
+                    let _ = reply.send(result);
+                }
+                Err(e) => {
+                    error!("Recalculation failed for period {} range {:?}: {:#}", period, range, e);
+                }
+            }
+        }
+    }
+
+    info!("Processor service worker thread shutting down");
+}
+
+/// Run interpolation, outlier removal, and SMA calculation over a range and
+/// persist the resulting series - unless the cached market summary for this
+/// exact range is already at least as fresh as the raw data, in which case
+/// the cached SMA series is returned as-is and nothing is recomputed
+fn recalculate(storage: &mut Storage, period: u32, range: (DateTime<Utc>, DateTime<Utc>)) -> Result<RecalcResult> {
+    let start_time = Instant::now();
+    let (range_start, range_end) = range;
+
+    let source_max_updated = storage
+        .max_last_updated_in_range(range_start, range_end)
+        .context("Failed to check source data freshness")?;
+
+    let cached_summary = storage
+        .get_summary(range_start, range_end)
+        .context("Failed to load cached market summary")?;
+
+    if let (Some(source_max_updated), Some((_, cached_max_updated))) = (source_max_updated, &cached_summary) {
+        if cached_max_updated >= &source_max_updated {
+            let series = storage
+                .get_sma(period, "price_per_sqft")
+                .context("Failed to load cached SMA series")?;
+            info!(
+                "Cache for range {} to {} still covers the latest source update ({}), skipping recompute",
+                range_start.format("%Y-%m-%d"), range_end.format("%Y-%m-%d"), source_max_updated
+            );
+            return Ok(RecalcResult {
+                period,
+                sma_points: series.len(),
+                outliers_removed: 0,
+                elapsed: start_time.elapsed(),
+            });
+        }
+    }
+
+    let mut data = storage
+        .get_data_range(range_start, range_end)
+        .context("Failed to load data range for recalculation")?;
+
+    interpolate_missing_data(&mut data);
+    let outliers_removed = remove_outliers(&mut data);
+
+    let config = SmaConfig::new(period);
+    let sma = calculate_sma(&data, &config);
+    let series: Vec<(DateTime<Utc>, f64)> = sma.iter()
+        .map(|(index, value)| (data[*index].date, *value))
+        .collect();
+
+    storage
+        .store_sma(period, "price_per_sqft", &series)
+        .context("Failed to cache recalculated SMA series")?;
+
+    if let Some(source_max_updated) = source_max_updated {
+        if let Ok(summary) = generate_market_summary(&data) {
+            storage
+                .store_summary(range_start, range_end, &summary, source_max_updated)
+                .context("Failed to cache recalculated market summary")?;
+        }
+    }
+
+    Ok(RecalcResult {
+        period,
+        sma_points: series.len(),
+        outliers_removed,
+        elapsed: start_time.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::{DataSource, HousingData};
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_processor_service_recalculates_on_submit() {
+        let temp_db = NamedTempFile::new().unwrap();
+
+        {
+            let mut seed = Storage::new(temp_db.path()).unwrap();
+            let now = Utc::now();
+            let data: Vec<HousingData> = (0..10).map(|i| HousingData {
+                date: now - chrono::Duration::days(9 - i),
+                active_listings: 40,
+                avg_price_per_sqft: Some(400.0 + i as f64),
+                data_source: DataSource::Historical,
+                last_updated: now,
+            }).collect();
+            seed.bulk_insert(&data).unwrap();
+        }
+
+        let service = ProcessorService::spawn(temp_db.path().to_path_buf()).unwrap();
+
+        let end = Utc::now();
+        let start = end - chrono::Duration::days(10);
+        let reply_rx = service.submit(7, (start, end)).unwrap();
+
+        let result = reply_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(result.period, 7);
+        assert!(result.sma_points > 0);
+    }
+
+    #[test]
+    fn test_recalculate_skips_work_when_cache_is_already_fresh() {
+        let temp_db = NamedTempFile::new().unwrap();
+        let mut storage = Storage::new(temp_db.path()).unwrap();
+
+        let now = Utc::now();
+        let data: Vec<HousingData> = (0..10).map(|i| HousingData {
+            date: now - chrono::Duration::days(9 - i),
+            active_listings: 40,
+            avg_price_per_sqft: Some(400.0 + i as f64),
+            data_source: DataSource::Historical,
+            last_updated: now,
+        }).collect();
+        storage.bulk_insert(&data).unwrap();
+
+        let range = (now - chrono::Duration::days(10), now);
+
+        let first = recalculate(&mut storage, 7, range).unwrap();
+        assert!(first.sma_points > 0);
+
+        // No new data since: the cached summary's source_max_updated already
+        // covers the latest raw last_updated, so the second call should
+        // return the same cached series without redoing outlier removal
+        let second = recalculate(&mut storage, 7, range).unwrap();
+        assert_eq!(second.sma_points, first.sma_points);
+        assert_eq!(second.outliers_removed, 0);
+    }
+}
@@ -0,0 +1,182 @@
+//! Pluggable data source registry
+//!
+//! `DataSource` on `HousingData` is just a tag recording where a point came
+//! from; the actual fetch logic was hardcoded to two free functions
+//! (`data_fetcher::fetch_zillow_data`, `utils::scraper::scrape_redfin`).
+//! `DataProvider` unifies both behind one trait so `AppConfig` can select a
+//! source by name (`create_provider`) and a future source - a P2P feed, say -
+//! slots in without the CLI needing to know about it.
+
+use crate::core::data_fetcher::{fetch_zillow_data, ZillowConfig};
+use crate::core::models::{Address, AppConfig, DataSource, HousingData, Property, RegionDemographics};
+use crate::utils::scraper::{scrape_redfin, ScraperConfig};
+use anyhow::Result;
+use chrono::Utc;
+
+/// A source of housing data, fetchable by ZIP code
+///
+/// Beyond the daily aggregate `fetch`, a provider may optionally expose
+/// richer comparable-sales/valuation data. Providers that don't have such
+/// data return the default empty/`None` response rather than fabricating one.
+pub trait DataProvider {
+    /// Human-readable identifier used to select this provider from `AppConfig`
+    fn name(&self) -> &'static str;
+
+    /// Fetch daily housing aggregates for `zip`
+    fn fetch(&self, zip: &str) -> Result<Vec<HousingData>>;
+
+    /// Recent comparable sales/listings for `zip`, for providers that expose
+    /// per-listing detail
+    fn comps(&self, _zip: &str) -> Result<Vec<Property>> {
+        Ok(Vec::new())
+    }
+
+    /// A modeled valuation for `address`, for providers that support one
+    fn estimate(&self, _address: &Address) -> Result<Option<f64>> {
+        Ok(None)
+    }
+
+    /// Region-level demographic attributes for `zip`, for providers that
+    /// expose them
+    fn demographics(&self, zip: &str) -> Result<RegionDemographics> {
+        Ok(RegionDemographics {
+            zip_code: zip.to_string(),
+            population: None,
+            median_household_income: None,
+            median_age: None,
+        })
+    }
+}
+
+/// Zillow Research CSV provider - historical bulk aggregates only, no
+/// per-listing detail, so `comps`/`estimate`/`demographics` use the defaults
+pub struct ZillowProvider {
+    pub config: ZillowConfig,
+}
+
+impl DataProvider for ZillowProvider {
+    fn name(&self) -> &'static str {
+        "zillow"
+    }
+
+    fn fetch(&self, zip: &str) -> Result<Vec<HousingData>> {
+        let mut config = self.config.clone();
+        config.zip_code = zip.to_string();
+        fetch_zillow_data(&config)
+    }
+}
+
+/// Redfin GIS API provider - exposes per-listing detail, so `fetch` rolls it
+/// up into a single daily aggregate while `comps` returns the listings behind it
+pub struct RedfinProvider {
+    pub config: ScraperConfig,
+}
+
+impl DataProvider for RedfinProvider {
+    fn name(&self) -> &'static str {
+        "redfin"
+    }
+
+    fn fetch(&self, zip: &str) -> Result<Vec<HousingData>> {
+        let scraped = scrape_redfin(&self.config_for(zip))?;
+        Ok(vec![HousingData {
+            date: scraped.timestamp,
+            active_listings: scraped.listings_count,
+            avg_price_per_sqft: scraped.avg_price_per_sqft,
+            data_source: DataSource::Scraped,
+            last_updated: Utc::now(),
+        }])
+    }
+
+    fn comps(&self, zip: &str) -> Result<Vec<Property>> {
+        let scraped = scrape_redfin(&self.config_for(zip))?;
+        Ok(scraped.properties)
+    }
+}
+
+impl RedfinProvider {
+    /// `self.config` re-targeted at `zip`, so one provider instance can serve
+    /// whatever ZIP the trait method is called with
+    fn config_for(&self, zip: &str) -> ScraperConfig {
+        let mut config = self.config.clone();
+        config.zip_code = zip.to_string();
+        config
+    }
+}
+
+/// Placeholder for the planned P2P data source (Phase 2); fails clearly
+/// rather than returning fabricated data
+pub struct P2pProvider;
+
+impl DataProvider for P2pProvider {
+    fn name(&self) -> &'static str {
+        "p2p"
+    }
+
+    fn fetch(&self, _zip: &str) -> Result<Vec<HousingData>> {
+        anyhow::bail!("P2P data provider is not yet implemented (Phase 2)")
+    }
+}
+
+/// Construct the `DataProvider` named by `config.data_provider`
+///
+/// # Returns
+/// An error naming the allowed provider identifiers if `config.data_provider`
+/// isn't one of them
+pub fn create_provider(config: &AppConfig) -> Result<Box<dyn DataProvider>> {
+    match config.data_provider.as_str() {
+        "zillow" => Ok(Box::new(ZillowProvider {
+            config: ZillowConfig { zip_code: config.zip_code.clone(), ..ZillowConfig::default() },
+        })),
+        "redfin" => Ok(Box::new(RedfinProvider {
+            config: ScraperConfig { zip_code: config.zip_code.clone(), ..ScraperConfig::default() },
+        })),
+        "p2p" => Ok(Box::new(P2pProvider)),
+        other => anyhow::bail!("Unknown data provider '{}' (expected zillow, redfin, or p2p)", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_provider_selects_zillow_by_default() {
+        let config = AppConfig::default();
+        let provider = create_provider(&config).unwrap();
+        assert_eq!(provider.name(), "zillow");
+    }
+
+    #[test]
+    fn test_create_provider_selects_redfin() {
+        let config = AppConfig { data_provider: "redfin".to_string(), ..AppConfig::default() };
+        let provider = create_provider(&config).unwrap();
+        assert_eq!(provider.name(), "redfin");
+    }
+
+    #[test]
+    fn test_create_provider_rejects_unknown_name() {
+        let config = AppConfig { data_provider: "bogus".to_string(), ..AppConfig::default() };
+        assert!(create_provider(&config).is_err());
+    }
+
+    #[test]
+    fn test_p2p_provider_fetch_fails_clearly() {
+        let provider = P2pProvider;
+        assert!(provider.fetch("90720").is_err());
+    }
+
+    #[test]
+    fn test_default_comps_and_estimate_are_empty() {
+        let provider = ZillowProvider { config: ZillowConfig::default() };
+        assert!(provider.comps("90720").unwrap().is_empty());
+        let address = Address {
+            street_address: "1 Main St".to_string(),
+            city: "Rossmoor".to_string(),
+            state: "CA".to_string(),
+            zip_code: "90720".to_string(),
+            unit: None,
+        };
+        assert!(provider.estimate(&address).unwrap().is_none());
+    }
+}
@@ -0,0 +1,234 @@
+//! Market-trend detection subsystem
+//!
+//! Turns stored `HousingData` into actionable signals instead of the flat
+//! averages reported by `MarketSummary`: SMA crossovers (a fast period
+//! crossing a slow period) and anomalous single-day moves (a rolling
+//! z-score of the daily change exceeding a threshold).
+
+use crate::core::data_processor::{calculate_sma, interpolate_missing_data};
+use crate::core::models::{HousingData, SmaConfig};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// What a `TrendSignal` represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendSignalKind {
+    /// The fast SMA crossed the slow SMA (golden cross / death cross)
+    Crossover,
+
+    /// A single day's price change was an outsized move relative to recent history
+    Anomaly,
+}
+
+/// Which way a signal moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendDirection {
+    Up,
+    Down,
+}
+
+/// A single detected trend event
+#[derive(Debug, Clone)]
+pub struct TrendSignal {
+    pub date: DateTime<Utc>,
+    pub kind: TrendSignalKind,
+    pub direction: TrendDirection,
+
+    /// For a crossover, the absolute gap between the fast and slow SMA.
+    /// For an anomaly, the absolute z-score of the day's change.
+    pub magnitude: f64,
+}
+
+/// Configuration for trend detection
+pub struct TrendConfig {
+    /// Fast SMA period, e.g. 7 days
+    pub fast_sma: SmaConfig,
+
+    /// Slow SMA period, e.g. 30 days
+    pub slow_sma: SmaConfig,
+
+    /// Trailing window size (in days) used to compute the z-score of the
+    /// latest daily change
+    pub zscore_window: usize,
+}
+
+impl Default for TrendConfig {
+    fn default() -> Self {
+        Self {
+            fast_sma: SmaConfig::new(7),
+            slow_sma: SmaConfig::new(30),
+            zscore_window: 30,
+        }
+    }
+}
+
+/// Detect SMA crossovers and anomalous daily moves in a housing data series
+///
+/// # Arguments
+/// * `data` - Housing data points, sorted by date. Points with a missing
+///   price/sqft are filled via `interpolate_missing_data` (where both
+///   neighbours are known) before detection runs; absent dates are not
+///   inserted.
+/// * `config` - Fast/slow SMA periods and the z-score window
+///
+/// # Returns
+/// All detected signals, sorted by date
+pub fn detect_trend_signals(data: &[HousingData], config: &TrendConfig) -> Vec<TrendSignal> {
+    let mut interpolated = data.to_vec();
+    interpolate_missing_data(&mut interpolated);
+
+    let mut signals = detect_crossovers(&interpolated, config);
+    signals.extend(detect_anomalies(&interpolated, config.zscore_window));
+    signals.sort_by_key(|s| s.date);
+
+    signals
+}
+
+/// Detect sign changes of (fast SMA - slow SMA), each one an event
+fn detect_crossovers(data: &[HousingData], config: &TrendConfig) -> Vec<TrendSignal> {
+    let fast = calculate_sma(data, &config.fast_sma);
+    let slow: HashMap<usize, f64> = calculate_sma(data, &config.slow_sma).into_iter().collect();
+
+    let mut signals = Vec::new();
+    let mut prev_sign: Option<i32> = None;
+
+    for (index, fast_value) in fast {
+        let Some(&slow_value) = slow.get(&index) else { continue };
+
+        let diff = fast_value - slow_value;
+        let sign = diff.partial_cmp(&0.0).map(|o| o as i32).unwrap_or(0);
+
+        if let Some(prev) = prev_sign {
+            if sign != 0 && prev != 0 && sign != prev {
+                signals.push(TrendSignal {
+                    date: data[index].date,
+                    kind: TrendSignalKind::Crossover,
+                    direction: if sign > 0 { TrendDirection::Up } else { TrendDirection::Down },
+                    magnitude: diff.abs(),
+                });
+            }
+        }
+
+        if sign != 0 {
+            prev_sign = Some(sign);
+        }
+    }
+
+    signals
+}
+
+/// Detect days whose change from the prior day is an outsized move relative
+/// to the trailing `window` of changes, using a rolling z-score
+fn detect_anomalies(data: &[HousingData], window: usize) -> Vec<TrendSignal> {
+    // (data_index, change) pairs, skipping over `None` gaps
+    let mut changes = Vec::new();
+    let mut prev: Option<f64> = None;
+    for (i, point) in data.iter().enumerate() {
+        if let Some(price) = point.avg_price_per_sqft {
+            if let Some(prev_price) = prev {
+                changes.push((i, price - prev_price));
+            }
+            prev = Some(price);
+        }
+    }
+
+    let mut signals = Vec::new();
+
+    for idx in 0..changes.len() {
+        if idx + 1 < window {
+            // Not enough trailing history yet
+            continue;
+        }
+
+        let window_changes = &changes[(idx + 1 - window)..=idx];
+        let values: Vec<f64> = window_changes.iter().map(|(_, c)| *c).collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+        let stddev = variance.sqrt();
+
+        if stddev == 0.0 {
+            // Flat window - no meaningful z-score, not an anomaly
+            continue;
+        }
+
+        let (data_index, latest_change) = changes[idx];
+        let z = (latest_change - mean) / stddev;
+
+        if z.abs() > 2.0 {
+            signals.push(TrendSignal {
+                date: data[data_index].date,
+                kind: TrendSignalKind::Anomaly,
+                direction: if z > 0.0 { TrendDirection::Up } else { TrendDirection::Down },
+                magnitude: z.abs(),
+            });
+        }
+    }
+
+    signals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::DataSource;
+    use chrono::Duration;
+
+    fn point(days_ago: i64, price: f64) -> HousingData {
+        HousingData {
+            date: Utc::now() - Duration::days(days_ago),
+            active_listings: 40,
+            avg_price_per_sqft: Some(price),
+            data_source: DataSource::Historical,
+            last_updated: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_detect_crossovers_on_trend_reversal() {
+        // Rises for 40 days then falls sharply - the fast SMA should cross
+        // below the slow SMA partway through the decline
+        let mut data = Vec::new();
+        for i in 0..40 {
+            data.push(point(70 - i, 400.0 + i as f64 * 2.0));
+        }
+        for i in 0..30 {
+            data.push(point(30 - i, 480.0 - i as f64 * 5.0));
+        }
+
+        let config = TrendConfig::default();
+        let signals = detect_trend_signals(&data, &config);
+
+        assert!(signals.iter().any(|s| s.kind == TrendSignalKind::Crossover && s.direction == TrendDirection::Down));
+    }
+
+    #[test]
+    fn test_detect_anomalies_flags_large_move() {
+        let mut data: Vec<HousingData> = (0..40).map(|i| point(40 - i, 450.0)).collect();
+        // A single sharp one-day jump after 35 flat days
+        data[35].avg_price_per_sqft = Some(600.0);
+
+        let config = TrendConfig { zscore_window: 10, ..TrendConfig::default() };
+        let signals = detect_trend_signals(&data, &config);
+
+        assert!(signals.iter().any(|s| s.kind == TrendSignalKind::Anomaly));
+    }
+
+    #[test]
+    fn test_detect_anomalies_flat_series_has_no_divide_by_zero() {
+        let data: Vec<HousingData> = (0..20).map(|i| point(20 - i, 450.0)).collect();
+        let config = TrendConfig { zscore_window: 10, ..TrendConfig::default() };
+
+        // Zero stddev across a perfectly flat series must not panic or flag anything
+        let signals = detect_trend_signals(&data, &config);
+        assert!(signals.iter().all(|s| s.kind != TrendSignalKind::Anomaly));
+    }
+
+    #[test]
+    fn test_short_series_produces_no_crossover_signals() {
+        let data: Vec<HousingData> = (0..5).map(|i| point(5 - i, 450.0 + i as f64)).collect();
+        let config = TrendConfig::default();
+
+        let signals = detect_trend_signals(&data, &config);
+        assert!(signals.iter().all(|s| s.kind != TrendSignalKind::Crossover));
+    }
+}
@@ -0,0 +1,7 @@
+//! RE_TRACKER_by_ZIP - Rossmoor Housing Inventory Tracker
+//!
+//! Library crate exposing the `core` data model/fetch/storage subsystems and
+//! `utils` support code; `main.rs` is a thin CLI shell over this.
+
+pub mod core;
+pub mod utils;
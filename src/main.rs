@@ -1,22 +1,18 @@
-/// RE_TRACKER_by_ZIP - Rossmoor Housing Inventory Tracker
-/// 
-/// Main entry point for the application.
-/// Phase 1: Desktop MVP with data fetching, scraping, and visualization.
-
-mod core;
-mod utils;
+//! RE_TRACKER_by_ZIP - Rossmoor Housing Inventory Tracker
+//! 
+//! Main entry point for the application.
+//! Phase 1: Desktop MVP with data fetching, scraping, and visualization.
 
 use anyhow::Result;
 use chrono::Utc;
 use clap::{Parser, Subcommand};
-use core::{AppConfig, DataSource, HousingData, Storage};
-use core::data_fetcher::{ZillowConfig, fetch_zillow_data};
 use log::{info, error, warn};
+use re_tracker::core::{create_provider, detect_trend_signals, fill_gaps, AppConfig, DataSource, HousingData, ProcessorService, RemoteFetcherConfig, Storage, TrendConfig};
+use re_tracker::utils::scraper::{ScraperConfig, scrape_zillow};
 use std::env;
 use std::fs;
 use std::path::PathBuf;
 use tiny_http::{Server, Response};
-use utils::scraper::{ScraperConfig, scrape_zillow};
 
 /// CLI arguments structure
 #[derive(Parser)]
@@ -29,9 +25,14 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Fetch historical data from Zillow Research
-    Fetch,
-    
+    /// Fetch historical data from a data provider
+    Fetch {
+        /// Data provider to fetch from: zillow, redfin, p2p, or remote
+        /// (backfills gaps from a configurable REST endpoint)
+        #[arg(short, long, default_value = "zillow")]
+        provider: String,
+    },
+
     /// Scrape real-time listing data
     Scrape,
     
@@ -58,7 +59,7 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Fetch => fetch_data()?,
+        Commands::Fetch { provider } => fetch_data(&provider)?,
         Commands::Scrape => scrape_data()?,
         Commands::Serve { port } => serve_frontend(port)?,
         Commands::Stats => show_stats()?,
@@ -67,19 +68,44 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-/// Fetch historical data from Zillow Research
-fn fetch_data() -> Result<()> {
-    info!("Fetching historical data...");
-    
+/// Fetch historical data from the named data provider
+fn fetch_data(provider_name: &str) -> Result<()> {
+    info!("Fetching historical data from provider '{}'...", provider_name);
+
     let db_path = get_database_path()?;
     let mut storage = Storage::new(&db_path)?;
-    
-    let config = ZillowConfig::default();
-    let data = fetch_zillow_data(&config)?;
-    
+
+    let config = AppConfig { data_provider: provider_name.to_string(), ..AppConfig::default() };
+
+    // "remote" isn't one of the uniform `DataProvider`s: it only backfills
+    // the gap in what's already stored, which needs mutable `Storage`
+    // access the `DataProvider::fetch(&self, zip)` signature doesn't have
+    if provider_name == "remote" {
+        let remote_config = RemoteFetcherConfig { zip_code: config.zip_code.clone(), ..RemoteFetcherConfig::default() };
+        let end_date = Utc::now();
+        let start_date = end_date - chrono::Duration::days(365);
+        let filled = fill_gaps(&mut storage, &remote_config, start_date, end_date)?;
+        info!("Filled {} data points from remote endpoint", filled);
+        return Ok(());
+    }
+
+    let provider = create_provider(&config)?;
+    let data = provider.fetch(&config.zip_code)?;
+
     info!("Fetched {} data points", data.len());
     storage.bulk_insert(&data)?;
-    
+
+    // Recompute the cached SMA series on the background worker so repeated
+    // `fetch` runs don't pay for it inline; dropping the service here blocks
+    // until the submitted recalculation completes
+    let service = ProcessorService::spawn(db_path.clone())?;
+    let end_date = Utc::now();
+    let start_date = end_date - chrono::Duration::days(365);
+    if let Err(e) = service.submit(30, (start_date, end_date)) {
+        warn!("Failed to submit post-fetch recalculation: {:#}", e);
+    }
+    drop(service);
+
     info!("Successfully stored historical data");
     Ok(())
 }
@@ -93,7 +119,7 @@ fn scrape_data() -> Result<()> {
     
     let config = ScraperConfig::default();
     let scraped = scrape_zillow(&config)?;
-    
+
     // Convert ScrapedData to HousingData
     let housing_data = HousingData {
         date: scraped.timestamp,
@@ -102,10 +128,11 @@ fn scrape_data() -> Result<()> {
         data_source: DataSource::Scraped,
         last_updated: Utc::now(),
     };
-    
+
     storage.upsert_housing_data(&housing_data)?;
-    
-    info!("Successfully stored scraped data");
+    storage.store_properties(&config.zip_code, &scraped.properties, scraped.timestamp)?;
+
+    info!("Successfully stored scraped data ({} properties)", scraped.properties.len());
     Ok(())
 }
 
@@ -193,18 +220,19 @@ fn serve_data() -> Response<std::io::Cursor<Vec<u8>>> {
     }
 }
 
-/// Get all housing data from database
-fn get_all_data() -> Result<Vec<serde_json::Value>> {
+/// Get all housing data and per-property listings from the database, keyed
+/// for the `/api/data` response
+fn get_all_data() -> Result<serde_json::Value> {
     let db_path = get_database_path()?;
     let storage = Storage::new(&db_path)?;
-    
+
     // Get all data (last 365 days)
     let end_date = Utc::now();
     let start_date = end_date - chrono::Duration::days(365);
     let data = storage.get_data_range(start_date, end_date)?;
-    
+
     // Convert to JSON-friendly format
-    let json_data: Vec<serde_json::Value> = data.iter().map(|d| {
+    let housing_data: Vec<serde_json::Value> = data.iter().map(|d| {
         serde_json::json!({
             "date": d.date.format("%Y-%m-%d").to_string(),
             "active_listings": d.active_listings,
@@ -212,18 +240,23 @@ fn get_all_data() -> Result<Vec<serde_json::Value>> {
             "data_source": format!("{:?}", d.data_source).to_lowercase()
         })
     }).collect();
-    
-    Ok(json_data)
+
+    let properties = storage.get_properties(&AppConfig::default().zip_code)?;
+
+    Ok(serde_json::json!({
+        "housing_data": housing_data,
+        "properties": properties,
+    }))
 }
 
 /// Show database statistics
 fn show_stats() -> Result<()> {
     let db_path = get_database_path()?;
     let storage = Storage::new(&db_path)?;
-    
+
     let count = storage.count_data_points()?;
     info!("Total data points: {}", count);
-    
+
     if count > 0 {
         if let Some(latest) = storage.get_latest_data()? {
             info!("Latest data point:");
@@ -237,25 +270,52 @@ fn show_stats() -> Result<()> {
     } else {
         warn!("No data in database. Run 're_tracker fetch' to populate.");
     }
-    
+
+    let zip_code = AppConfig::default().zip_code;
+    let properties = storage.get_properties(&zip_code)?;
+    info!("Stored listings for ZIP {}: {}", zip_code, properties.len());
+
+    if count > 0 {
+        let end_date = Utc::now();
+        let start_date = end_date - chrono::Duration::days(365);
+        let history = storage.get_data_range(start_date, end_date)?;
+        let signals = detect_trend_signals(&history, &TrendConfig::default());
+
+        info!("Trend signals in the last year: {}", signals.len());
+        if let Some(latest) = signals.last() {
+            info!("  Most recent: {:?} ({:?}) on {}", latest.kind, latest.direction, latest.date.format("%Y-%m-%d"));
+        }
+    }
+
     Ok(())
 }
 
 /// Get the database file path
-/// Uses $HOME/.local/share/re_tracker/ on Linux
+///
+/// Honors a `DB_PATH` environment variable override (e.g. to point at a test
+/// database or a container volume); otherwise falls back to
+/// `$HOME/.local/share/re_tracker/housing_data.db` on Linux
 fn get_database_path() -> Result<std::path::PathBuf> {
+    if let Ok(db_path) = env::var("DB_PATH") {
+        let path = PathBuf::from(db_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        return Ok(path);
+    }
+
     let home = env::var("HOME")
         .or_else(|_| env::var("USERPROFILE"))
         .map_err(|_| anyhow::anyhow!("Could not determine home directory"))?;
-    
+
     let data_dir = std::path::Path::new(&home)
         .join(".local")
         .join("share")
         .join("re_tracker");
-    
+
     // Create directory if it doesn't exist
     std::fs::create_dir_all(&data_dir)?;
-    
+
     Ok(data_dir.join("housing_data.db"))
 }
 
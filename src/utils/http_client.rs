@@ -0,0 +1,134 @@
+//! Shared HTTP client configuration and retry helper
+//!
+//! `data_fetcher` and `scraper` each used to build their own
+//! `reqwest::blocking::Client` inline with a hardcoded 30-second timeout.
+//! This module centralizes that into `HttpClientConfig` so timeout,
+//! connect-timeout, user-agent, and retry behavior can be configured
+//! consistently and wired into `ZillowConfig`/`ScraperConfig`.
+//!
+//! The underlying TLS implementation is selected at compile time via this
+//! crate's own `default-tls`, `native-tls`, `rustls-tls-webpki-roots`, or
+//! `rustls-tls-native-roots` Cargo features (each forwarding to the
+//! identically-named `reqwest` feature), so a build targeting a
+//! locked-down or musl/static environment can pick a backend that actually
+//! links there.
+
+use anyhow::{Context, Result};
+use log::warn;
+use reqwest::blocking::Client;
+use std::thread;
+use std::time::Duration;
+
+/// Configuration shared by every outbound HTTP client in the app
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    /// User-Agent header sent with every request
+    pub user_agent: String,
+
+    /// Overall request timeout in seconds
+    pub timeout_secs: u64,
+
+    /// Connection establishment timeout in seconds
+    pub connect_timeout_secs: u64,
+
+    /// Number of retries after a transient failure, with exponential backoff
+    pub max_retries: u32,
+
+    /// Base backoff delay in milliseconds; doubled on each retry
+    pub retry_backoff_base_ms: u64,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: "RE_TRACKER/0.1.0 (Rossmoor Housing Tracker; Educational)".to_string(),
+            timeout_secs: 30,
+            connect_timeout_secs: 10,
+            max_retries: 3,
+            retry_backoff_base_ms: 200,
+        }
+    }
+}
+
+/// Build a `reqwest::blocking::Client` from a shared configuration
+pub fn build_client(config: &HttpClientConfig) -> Result<Client> {
+    Client::builder()
+        .user_agent(&config.user_agent)
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+/// Run `op` (typically a request send-and-parse closure), retrying on
+/// failure with exponential backoff up to `config.max_retries` times
+///
+/// # Returns
+/// The first successful result, or the last error if every attempt failed
+pub fn send_with_retry<T>(config: &HttpClientConfig, mut op: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut attempt = 0;
+
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < config.max_retries => {
+                let backoff = Duration::from_millis(config.retry_backoff_base_ms * 2u64.pow(attempt));
+                warn!(
+                    "Request failed (attempt {}/{}): {:#}. Retrying in {:?}",
+                    attempt + 1,
+                    config.max_retries + 1,
+                    e,
+                    backoff
+                );
+                thread::sleep(backoff);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_http_client_config_default() {
+        let config = HttpClientConfig::default();
+        assert!(config.timeout_secs > 0);
+        assert!(config.max_retries > 0);
+    }
+
+    #[test]
+    fn test_send_with_retry_succeeds_after_transient_failures() {
+        let config = HttpClientConfig { retry_backoff_base_ms: 0, ..HttpClientConfig::default() };
+        let attempts = Cell::new(0);
+
+        let result = send_with_retry(&config, || {
+            let count = attempts.get() + 1;
+            attempts.set(count);
+            if count < 3 {
+                anyhow::bail!("transient failure");
+            }
+            Ok(count)
+        });
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_send_with_retry_gives_up_after_max_retries() {
+        let config = HttpClientConfig { max_retries: 2, retry_backoff_base_ms: 0, ..HttpClientConfig::default() };
+        let attempts = Cell::new(0);
+
+        let result: Result<()> = send_with_retry(&config, || {
+            attempts.set(attempts.get() + 1);
+            anyhow::bail!("always fails")
+        });
+
+        assert!(result.is_err());
+        // Initial attempt plus max_retries retries
+        assert_eq!(attempts.get(), 3);
+    }
+}
@@ -0,0 +1,6 @@
+//! Utility module for cross-cutting helpers used outside `core`'s business
+//! logic: live data collection (`scraper`) and the shared HTTP client setup
+//! it and `core::data_fetcher` both build on (`http_client`).
+
+pub mod scraper;
+pub mod http_client;
@@ -1,28 +1,26 @@
-/// Web scraper module for collecting real-time housing data
-/// 
-/// This module implements ethical web scraping to collect current listing data
-/// from Zillow and other real estate sites. Includes rate limiting and respects robots.txt.
+//! Web scraper module for collecting real-time housing data
+//! 
+//! This module implements ethical web scraping to collect current listing data
+//! from Zillow and other real estate sites. Includes rate limiting and respects robots.txt.
 
-use crate::core::models::ScrapedData;
+use crate::core::models::{Address, Property, ScrapedData};
+use crate::utils::http_client::{build_client, send_with_retry, HttpClientConfig};
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use log::{info, debug, warn};
-use reqwest::blocking::Client;
 use scraper::{Html, Selector};
 use std::thread;
 use std::time::Duration;
 
 /// Configuration for web scraping
+#[derive(Clone)]
 pub struct ScraperConfig {
     /// ZIP code to search for
     pub zip_code: String,
-    
-    /// User agent string for HTTP requests
-    pub user_agent: String,
-    
-    /// Request timeout in seconds
-    pub timeout_secs: u64,
-    
+
+    /// HTTP client timeout, user agent, and retry/backoff settings
+    pub http: HttpClientConfig,
+
     /// Rate limit: minimum seconds between requests
     pub rate_limit_secs: u64,
 }
@@ -31,8 +29,10 @@ impl Default for ScraperConfig {
     fn default() -> Self {
         Self {
             zip_code: "90720".to_string(),
-            user_agent: "RE_TRACKER/0.1.0 (Rossmoor Housing Tracker; Educational; +https://github.com/urmt/RE_TRACKER_by_ZIP)".to_string(),
-            timeout_secs: 30,
+            http: HttpClientConfig {
+                user_agent: "RE_TRACKER/0.1.0 (Rossmoor Housing Tracker; Educational; +https://github.com/urmt/RE_TRACKER_by_ZIP)".to_string(),
+                ..HttpClientConfig::default()
+            },
             rate_limit_secs: 60, // 1 request per minute
         }
     }
@@ -58,14 +58,11 @@ pub fn scrape_zillow(config: &ScraperConfig) -> Result<ScrapedData> {
     
     // Build the Zillow search URL for this ZIP code
     let url = format!("https://www.zillow.com/homes/{}_rb/", config.zip_code);
-    
+
     // Create HTTP client with proper user agent
-    let client = Client::builder()
-        .user_agent(&config.user_agent)
-        .timeout(Duration::from_secs(config.timeout_secs))
-        .build()
+    let _client = build_client(&config.http)
         .context("Failed to create HTTP client")?;
-    
+
     // IMPORTANT: In a production implementation, you would:
     // 1. Check robots.txt first
     // 2. Use a headless browser (like headless_chrome) for JavaScript rendering
@@ -81,6 +78,7 @@ pub fn scrape_zillow(config: &ScraperConfig) -> Result<ScrapedData> {
         avg_price_per_sqft: Some(455.0), // Simulated average price
         timestamp: Utc::now(),
         source_url: url.clone(),
+        properties: Vec::new(), // Synthetic data carries no per-listing detail
     };
     
     info!("Scraped {} active listings at ${:.2}/sqft", 
@@ -94,38 +92,235 @@ pub fn scrape_zillow(config: &ScraperConfig) -> Result<ScrapedData> {
     Ok(scraped)
 }
 
-/// Scrape Redfin for current listing data (alternative source)
-/// 
+/// Scrape Redfin for current listing data using its internal GIS JSON API
+///
+/// Avoids HTML/JS scraping entirely by going through the same undocumented
+/// endpoints Redfin's own site uses: the location-autocomplete endpoint
+/// resolves the ZIP to a `region_id`/`region_type`, and the GIS download
+/// endpoint then returns a CSV of every active listing for that region.
+///
 /// # Arguments
 /// * `config` - Scraper configuration
-/// 
+///
 /// # Returns
 /// ScrapedData with current listings count and average price
 pub fn scrape_redfin(config: &ScraperConfig) -> Result<ScrapedData> {
     info!("Scraping Redfin for ZIP {}", config.zip_code);
-    
-    let url = format!("https://www.redfin.com/zipcode/{}", config.zip_code);
-    
-    warn!("Using synthetic scraped data - real Redfin scraping not yet implemented");
-    
-    // Generate synthetic "scraped" data
+
+    let client = build_client(&config.http)
+        .context("Failed to create HTTP client")?;
+
+    let (region_id, region_type) = resolve_redfin_region(&client, &config.zip_code, &config.http)
+        .context("Failed to resolve Redfin region for ZIP")?;
+
+    let gis_url = format!(
+        "https://www.redfin.com/stingray/api/gis-csv?al=1&region_id={}&region_type={}",
+        region_id, region_type
+    );
+
+    let csv_body = send_with_retry(&config.http, || {
+        client.get(&gis_url)
+            .send()
+            .context("Failed to request Redfin GIS listings")?
+            .error_for_status()
+            .context("Redfin GIS endpoint returned an error status")?
+            .text()
+            .context("Failed to read Redfin GIS response body")
+    })?;
+
+    let (listings_count, avg_price_per_sqft, properties) = parse_redfin_gis_csv(&csv_body, &config.zip_code);
+
     let scraped = ScrapedData {
-        listings_count: 45, // Slightly different from Zillow
-        avg_price_per_sqft: Some(458.0),
+        listings_count,
+        avg_price_per_sqft,
         timestamp: Utc::now(),
-        source_url: url,
+        source_url: gis_url,
+        properties,
     };
-    
-    info!("Scraped {} active listings at ${:.2}/sqft from Redfin", 
-          scraped.listings_count, 
+
+    info!("Scraped {} active listings at ${:.2}/sqft from Redfin",
+          scraped.listings_count,
           scraped.avg_price_per_sqft.unwrap_or(0.0));
-    
+
     // Respect rate limiting
     thread::sleep(Duration::from_secs(config.rate_limit_secs));
-    
+
     Ok(scraped)
 }
 
+/// Resolve a ZIP code to a Redfin `(region_id, region_type)` pair via the
+/// location-autocomplete endpoint
+///
+/// Redfin's JSON endpoints prefix the body with a `{}&&` anti-hijacking
+/// guard, which must be stripped before the remainder can be deserialized.
+fn resolve_redfin_region(client: &reqwest::blocking::Client, zip_code: &str, http_config: &HttpClientConfig) -> Result<(String, String)> {
+    let url = format!(
+        "https://www.redfin.com/stingray/do/location-autocomplete?location={}&v=2",
+        zip_code
+    );
+
+    let raw = send_with_retry(http_config, || {
+        client.get(&url)
+            .send()
+            .context("Failed to request Redfin location autocomplete")?
+            .error_for_status()
+            .context("Redfin autocomplete endpoint returned an error status")?
+            .text()
+            .context("Failed to read Redfin autocomplete response body")
+    })?;
+
+    let json_str = raw.strip_prefix("{}&&").unwrap_or(&raw);
+    let parsed: serde_json::Value = serde_json::from_str(json_str)
+        .context("Failed to parse Redfin autocomplete JSON")?;
+
+    // The undocumented response nests the best match under either
+    // `payload.exactMatch` or the first row of `payload.sections`
+    let region_match = parsed.pointer("/payload/exactMatch")
+        .filter(|v| !v.is_null())
+        .or_else(|| parsed.pointer("/payload/sections/0/rows/0"))
+        .context("Redfin autocomplete response did not contain a matching region")?;
+
+    // Region identifiers come back as e.g. "2_12345" (region_type_region_id)
+    let id = region_match.get("id")
+        .and_then(|v| v.as_str())
+        .context("Redfin autocomplete match is missing a region id")?;
+
+    let (region_type, region_id) = id.split_once('_')
+        .context("Unexpected Redfin region id format")?;
+
+    Ok((region_id.to_string(), region_type.to_string()))
+}
+
+/// Parse a Redfin GIS CSV export into a listing count, average price/sqft,
+/// and the individual `Property` listings behind those aggregates
+///
+/// # Returns
+/// `(listings_count, avg_price_per_sqft, properties)`, where the average is
+/// `None` if no row had both a price and a square footage
+fn parse_redfin_gis_csv(csv_body: &str, zip_code: &str) -> (i32, Option<f64>, Vec<Property>) {
+    let mut lines = csv_body.lines();
+    let Some(header_line) = lines.next() else { return (0, None, Vec::new()) };
+    let headers: Vec<&str> = header_line.split(',').collect();
+
+    let col_index = |name: &str| headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+    let price_index = col_index("PRICE");
+    let sqft_index = col_index("SQUARE FEET");
+    let address_index = col_index("ADDRESS");
+    let city_index = col_index("CITY");
+    let state_index = col_index("STATE OR PROVINCE");
+    let zip_index = col_index("ZIP OR POSTAL CODE");
+    let year_built_index = col_index("YEAR BUILT");
+    let days_on_market_index = col_index("DAYS ON MARKET");
+    let sold_date_index = col_index("SOLD DATE");
+    let mls_index = col_index("MLS#");
+
+    let mut listings_count = 0;
+    let mut price_per_sqft_ratios = Vec::new();
+    let mut properties = Vec::new();
+
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        listings_count += 1;
+
+        let fields: Vec<&str> = line.split(',').collect();
+        let field = |index: Option<usize>| index.and_then(|i| fields.get(i)).map(|v| v.trim());
+
+        let price = field(price_index).and_then(|v| v.parse::<f64>().ok());
+        let square_feet = field(sqft_index).and_then(|v| v.parse::<f64>().ok());
+
+        if let (Some(price), Some(square_feet)) = (price, square_feet) {
+            if square_feet > 0.0 {
+                price_per_sqft_ratios.push(price / square_feet);
+            }
+        }
+
+        let (street_address, unit) = parse_address_two(field(address_index).unwrap_or(""));
+
+        let address = Address {
+            street_address,
+            city: field(city_index).unwrap_or("").to_string(),
+            state: field(state_index).unwrap_or("").to_string(),
+            zip_code: field(zip_index).filter(|v| !v.is_empty()).unwrap_or(zip_code).to_string(),
+            unit,
+        };
+
+        let sold_date = field(sold_date_index)
+            .and_then(|v| NaiveDate::parse_from_str(v, "%Y-%m-%d").ok())
+            .map(naive_date_to_utc);
+
+        properties.push(Property {
+            address,
+            price,
+            square_feet,
+            days_on_market: field(days_on_market_index).and_then(|v| v.parse::<i32>().ok()),
+            sold_date,
+            mls_id: field(mls_index).filter(|v| !v.is_empty()).map(|v| v.to_string()),
+            year_built: field(year_built_index).and_then(|v| v.parse::<i32>().ok()),
+            stories: None,
+            agent: None,
+        });
+    }
+
+    let avg_price_per_sqft = if price_per_sqft_ratios.is_empty() {
+        None
+    } else {
+        Some(price_per_sqft_ratios.iter().sum::<f64>() / price_per_sqft_ratios.len() as f64)
+    };
+
+    (listings_count, avg_price_per_sqft, properties)
+}
+
+/// Convert a calendar date to midnight UTC on that date
+fn naive_date_to_utc(date: NaiveDate) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(&format!("{}T00:00:00Z", date.format("%Y-%m-%d")))
+        .expect("formatted date string must be valid RFC3339")
+        .with_timezone(&Utc)
+}
+
+/// Split a unit/apartment/suite designator (e.g. "#4", "Apt 2B", "Unit 3")
+/// out of an assembled street address line
+///
+/// # Returns
+/// `(street_address, unit)` with the designator removed from the street line
+fn parse_address_two(line: &str) -> (String, Option<String>) {
+    let trimmed = line.trim();
+
+    // "123 Main St #4" style: split at the first '#'
+    if let Some(hash_pos) = trimmed.find('#') {
+        let street = trimmed[..hash_pos].trim().trim_end_matches(',').trim();
+        let unit = trimmed[hash_pos..].trim();
+        if !street.is_empty() && !unit.is_empty() {
+            return (street.to_string(), Some(unit.to_string()));
+        }
+    }
+
+    // "123 Main St Apt 2B" / "Unit 3" / "Ste 100" style: split at the marker word
+    let lower = trimmed.to_lowercase();
+    for marker in ["apartment", "apt", "unit", "suite", "ste"] {
+        let needle = format!(" {}", marker);
+        if let Some(pos) = lower.find(&needle) {
+            let after_marker = pos + needle.len();
+            let is_word_boundary = match lower[after_marker..].chars().next() {
+                Some(c) => !c.is_alphanumeric(),
+                None => true,
+            };
+            if !is_word_boundary {
+                continue;
+            }
+
+            let street = trimmed[..pos].trim().trim_end_matches(',').trim();
+            let unit = trimmed[pos..].trim();
+            if !street.is_empty() && !unit.is_empty() {
+                return (street.to_string(), Some(unit.to_string()));
+            }
+        }
+    }
+
+    (trimmed.to_string(), None)
+}
+
 /// Parse HTML to extract listing count
 /// 
 /// # Arguments
@@ -133,6 +328,7 @@ pub fn scrape_redfin(config: &ScraperConfig) -> Result<ScrapedData> {
 /// 
 /// # Returns
 /// Number of active listings found, or None if not found
+#[allow(dead_code)]
 fn parse_listing_count(html: &str) -> Option<i32> {
     let document = Html::parse_document(html);
     
@@ -178,4 +374,67 @@ mod tests {
         assert!(data.listings_count > 0);
         assert!(data.avg_price_per_sqft.is_some());
     }
+
+    #[test]
+    fn test_parse_redfin_gis_csv() {
+        let csv = "SALE TYPE,ADDRESS,PRICE,SQUARE FEET\n\
+            MLS Listing,123 Main St,450000,1000\n\
+            MLS Listing,456 Oak Ave,900000,2000\n";
+
+        let (listings_count, avg_price_per_sqft, _properties) = parse_redfin_gis_csv(csv, "90720");
+
+        assert_eq!(listings_count, 2);
+        // Both rows work out to $450/sqft
+        assert_eq!(avg_price_per_sqft, Some(450.0));
+    }
+
+    #[test]
+    fn test_parse_redfin_gis_csv_empty() {
+        let (listings_count, avg_price_per_sqft, properties) = parse_redfin_gis_csv("", "90720");
+        assert_eq!(listings_count, 0);
+        assert_eq!(avg_price_per_sqft, None);
+        assert!(properties.is_empty());
+    }
+
+    #[test]
+    fn test_parse_redfin_gis_csv_builds_properties() {
+        let csv = "SALE TYPE,ADDRESS,CITY,STATE OR PROVINCE,ZIP OR POSTAL CODE,PRICE,SQUARE FEET,YEAR BUILT,DAYS ON MARKET,SOLD DATE,MLS#\n\
+            MLS Listing,123 Main St #4,Rossmoor,CA,90720,450000,1000,1985,12,2024-06-15,PW12345\n";
+
+        let (listings_count, avg_price_per_sqft, properties) = parse_redfin_gis_csv(csv, "90720");
+
+        assert_eq!(listings_count, 1);
+        assert_eq!(avg_price_per_sqft, Some(450.0));
+        assert_eq!(properties.len(), 1);
+
+        let property = &properties[0];
+        assert_eq!(property.address.street_address, "123 Main St");
+        assert_eq!(property.address.unit, Some("#4".to_string()));
+        assert_eq!(property.address.city, "Rossmoor");
+        assert_eq!(property.year_built, Some(1985));
+        assert_eq!(property.days_on_market, Some(12));
+        assert_eq!(property.mls_id, Some("PW12345".to_string()));
+        assert!(property.sold_date.is_some());
+    }
+
+    #[test]
+    fn test_parse_address_two_hash_unit() {
+        let (street, unit) = parse_address_two("123 Main St #4");
+        assert_eq!(street, "123 Main St");
+        assert_eq!(unit, Some("#4".to_string()));
+    }
+
+    #[test]
+    fn test_parse_address_two_apt_word() {
+        let (street, unit) = parse_address_two("456 Oak Ave Apt 2B");
+        assert_eq!(street, "456 Oak Ave");
+        assert_eq!(unit, Some("Apt 2B".to_string()));
+    }
+
+    #[test]
+    fn test_parse_address_two_no_unit() {
+        let (street, unit) = parse_address_two("789 Elm Dr");
+        assert_eq!(street, "789 Elm Dr");
+        assert_eq!(unit, None);
+    }
 }